@@ -0,0 +1,30 @@
+#[macro_use]
+extern crate honggfuzz;
+
+use lnpbp::rgb::Consignment;
+use lnpbp::strict_encoding::{StrictDecode, StrictEncode};
+
+fn main() {
+    loop {
+        fuzz!(|data: &[u8]| {
+            let mut consignment = match Consignment::strict_decode(data) {
+                Ok(consignment) => consignment,
+                Err(_) => return,
+            };
+
+            let mut encoded = Vec::new();
+            consignment
+                .strict_encode(&mut encoded)
+                .expect("a successfully decoded consignment must re-encode");
+            assert_eq!(
+                encoded,
+                data[..encoded.len()],
+                "strict_decode/strict_encode round-trip mismatch"
+            );
+
+            let _ = consignment.txids();
+            let _ = consignment.node_ids();
+            let _ = consignment.reveal_seals(std::iter::empty());
+        });
+    }
+}
@@ -11,7 +11,8 @@
 // along with this software.
 // If not, see <https://opensource.org/licenses/MIT>.
 
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
+use std::io;
 
 use bitcoin::Txid;
 
@@ -21,6 +22,7 @@ use crate::rgb::{
     validation, Anchor, Extension, Genesis, Node, NodeId, Schema, Transition,
     Validator,
 };
+use crate::strict_encoding::{self, StrictDecode, StrictEncode};
 
 pub type ConsignmentEndpoints = Vec<(NodeId, bp::blind::OutpointHash)>;
 pub type TransitionData = Vec<(Anchor, Transition)>;
@@ -28,7 +30,12 @@ pub type ExtensionData = Vec<Extension>;
 
 pub const RGB_CONSIGNMENT_VERSION: u16 = 0;
 
-#[derive(Clone, Debug, Display, StrictEncode, StrictDecode)]
+/// Highest consignment version this build of the library is able to
+/// decode. Consignments carrying a newer version must be rejected rather
+/// than misparsed, since their field layout may differ from what we know.
+pub const MAX_SUPPORTED_VERSION: u16 = RGB_CONSIGNMENT_VERSION;
+
+#[derive(Clone, Debug, Display, StrictEncode)]
 #[strict_crate(crate)]
 #[display(Debug)]
 pub struct Consignment {
@@ -55,6 +62,29 @@ impl Consignment {
         }
     }
 
+    /// Version of the consignment's on-wire layout, as read from (or
+    /// written to) the leading `u16` of the strict-encoded data.
+    #[inline]
+    pub fn version(&self) -> u16 { self.version }
+
+    /// Scaffolding for upgrading an in-memory consignment produced under
+    /// an older version to the current on-wire layout, filling
+    /// newly-introduced fields with their defaults.
+    ///
+    /// There is currently no way to construct or decode a `Consignment`
+    /// with `version < RGB_CONSIGNMENT_VERSION` (`version` is private,
+    /// and decoding only ever produces the current version), so this
+    /// method has no upgrade branch to run yet and is a no-op in
+    /// practice. It exists so that the day a second version lands, there
+    /// is already a single place to thread its upgrade step through
+    /// rather than scattering version checks across callers.
+    pub fn migrate(mut self) -> Consignment {
+        if self.version < RGB_CONSIGNMENT_VERSION {
+            self.version = RGB_CONSIGNMENT_VERSION;
+        }
+        self
+    }
+
     #[inline]
     pub fn txids(&self) -> BTreeSet<Txid> {
         self.state_transitions
@@ -109,6 +139,199 @@ impl Consignment {
         }
         counter
     }
+
+    /// Extracts the minimal sub-consignment needed to prove `endpoint`:
+    /// genesis, plus every `(Anchor, Transition)`/`Extension` reachable
+    /// backward from `endpoint` through owned-right ancestor references.
+    /// Nodes not on any such path are dropped entirely; individual
+    /// owned-right assignments of a kept node that no kept descendant
+    /// spends are concealed rather than revealed (down to the specific
+    /// index within a type, so a pruned sibling sharing its assignment
+    /// type with a kept one doesn't leak), since the commitment still
+    /// has to cover them.
+    ///
+    /// The result carries only the single `endpoints` entry matching
+    /// `endpoint`, and validates against the same schema as `self`.
+    pub fn prune_to(&self, endpoint: &NodeId) -> Consignment {
+        let mut keep: BTreeSet<NodeId> = bset![*endpoint];
+        let mut queue = vec![*endpoint];
+        while let Some(node_id) = queue.pop() {
+            let parents: Vec<NodeId> = self
+                .state_transitions
+                .iter()
+                .find(|(_, transition)| transition.node_id() == node_id)
+                .map(|(_, transition)| {
+                    transition.ancestors().keys().copied().collect()
+                })
+                .or_else(|| {
+                    self.state_extensions
+                        .iter()
+                        .find(|extension| extension.node_id() == node_id)
+                        .map(|extension| {
+                            extension.ancestors().keys().copied().collect()
+                        })
+                })
+                .unwrap_or_default();
+            for parent in parents {
+                if keep.insert(parent) {
+                    queue.push(parent);
+                }
+            }
+        }
+
+        // For each (parent, assignment type), the specific indices some
+        // kept node still references as an ancestor are the ones a
+        // descendant on our path actually spends; everything else under
+        // that same type is a sibling allocation we never walked into
+        // and must be concealed index-by-index, not by whole type
+        // bucket, or a pruned sibling's seal would leak in full.
+        let required = Self::required_indices(
+            self.state_transitions
+                .iter()
+                .filter(|(_, transition)| keep.contains(&transition.node_id()))
+                .map(|(_, transition)| transition.ancestors())
+                .chain(
+                    self.state_extensions
+                        .iter()
+                        .filter(|extension| {
+                            keep.contains(&extension.node_id())
+                        })
+                        .map(|extension| extension.ancestors()),
+                ),
+        );
+        let no_indices = BTreeSet::new();
+
+        let state_transitions: TransitionData = self
+            .state_transitions
+            .iter()
+            .filter(|(_, transition)| keep.contains(&transition.node_id()))
+            .cloned()
+            .map(|(anchor, mut transition)| {
+                let node_id = transition.node_id();
+                for (ty, assignment) in transition.owned_rights_mut() {
+                    let keep_indices = required
+                        .get(&(node_id, *ty))
+                        .unwrap_or(&no_indices);
+                    assignment.conceal_seals_except(keep_indices);
+                }
+                (anchor, transition)
+            })
+            .collect();
+
+        let state_extensions: ExtensionData = self
+            .state_extensions
+            .iter()
+            .filter(|extension| keep.contains(&extension.node_id()))
+            .cloned()
+            .map(|mut extension| {
+                let node_id = extension.node_id();
+                for (ty, assignment) in extension.owned_rights_mut() {
+                    let keep_indices = required
+                        .get(&(node_id, *ty))
+                        .unwrap_or(&no_indices);
+                    assignment.conceal_seals_except(keep_indices);
+                }
+                extension
+            })
+            .collect();
+
+        let endpoints: ConsignmentEndpoints = self
+            .endpoints
+            .iter()
+            .filter(|(node_id, _)| node_id == endpoint)
+            .cloned()
+            .collect();
+
+        let mut genesis = self.genesis.clone();
+        let genesis_id = genesis.node_id();
+        for (ty, assignment) in genesis.owned_rights_mut() {
+            let keep_indices =
+                required.get(&(genesis_id, *ty)).unwrap_or(&no_indices);
+            assignment.conceal_seals_except(keep_indices);
+        }
+
+        Consignment {
+            version: self.version,
+            genesis,
+            endpoints,
+            state_transitions,
+            state_extensions,
+        }
+    }
+
+    /// For a set of kept nodes' ancestor maps, collects which
+    /// `(parent, assignment type, index)` triples are actually consumed
+    /// — i.e. which specific assignment indices, not whole assignment
+    /// types, a kept descendant still points back to. Pulled out of
+    /// [`Self::prune_to`] so the type-vs-index distinction it depends on
+    /// can be exercised directly in a test without needing a full
+    /// transition/extension graph.
+    fn required_indices<'a>(
+        kept_ancestors: impl Iterator<
+            Item = &'a BTreeMap<NodeId, BTreeMap<u16, Vec<u16>>>,
+        >,
+    ) -> BTreeMap<(NodeId, u16), BTreeSet<u16>> {
+        let mut required: BTreeMap<(NodeId, u16), BTreeSet<u16>> =
+            BTreeMap::new();
+        for ancestors in kept_ancestors {
+            for (parent, assignments) in ancestors {
+                for (ty, indices) in assignments {
+                    required
+                        .entry((*parent, *ty))
+                        .or_default()
+                        .extend(indices.iter().copied());
+                }
+            }
+        }
+        required
+    }
+}
+
+impl StrictDecode for Consignment {
+    type Error = strict_encoding::Error;
+
+    fn strict_decode<D: io::Read>(mut d: D) -> Result<Self, Self::Error> {
+        let version = u16::strict_decode(&mut d)?;
+        if version > MAX_SUPPORTED_VERSION {
+            return Err(strict_encoding::Error::DataIntegrityError(format!(
+                "consignment version {} is not supported by this version \
+                 of the library, which understands versions up to {}",
+                version, MAX_SUPPORTED_VERSION
+            )));
+        }
+        Self::decode_fields(version, d)
+    }
+}
+
+impl Consignment {
+    /// Decodes the fields following the version prefix according to the
+    /// layout used by `version`. Versions are matched explicitly (rather
+    /// than falling through to a default) so that adding a new version's
+    /// layout can't accidentally decode an older one with the wrong
+    /// fields.
+    fn decode_fields<D: io::Read>(
+        version: u16,
+        mut d: D,
+    ) -> Result<Self, strict_encoding::Error> {
+        match version {
+            0 => Ok(Self {
+                version,
+                genesis: Genesis::strict_decode(&mut d)?,
+                endpoints: ConsignmentEndpoints::strict_decode(&mut d)?,
+                state_transitions: TransitionData::strict_decode(&mut d)?,
+                state_extensions: ExtensionData::strict_decode(&mut d)?,
+            }),
+            unsupported => Err(strict_encoding::Error::DataIntegrityError(
+                format!(
+                    "consignment version {} has no decoder registered in \
+                     `decode_fields`, even though it is within \
+                     MAX_SUPPORTED_VERSION ({}); this is a bug in the \
+                     library, not in the supplied data",
+                    unsupported, MAX_SUPPORTED_VERSION
+                ),
+            )),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -235,7 +458,7 @@ pub(crate) mod test {
             &self,
             txid: &Txid,
         ) -> Result<
-            Option<(bitcoin::Transaction, u64)>,
+            Option<validation::ResolvedTx>,
             validation::TxResolverError,
         > {
             eprintln!("Validating txid {}", txid);
@@ -250,4 +473,77 @@ pub(crate) mod test {
         let status = consignment.validate(&schema, TestResolver);
         println!("{}", status);
     }
+
+    #[test]
+    fn test_consignment_version() {
+        assert_eq!(consignment().version(), RGB_CONSIGNMENT_VERSION);
+    }
+
+    #[test]
+    fn test_consignment_rejects_future_version() {
+        let mut data = [0u8; 2 + 2];
+        data[..2].copy_from_slice(&(MAX_SUPPORTED_VERSION + 1).to_le_bytes());
+        assert!(Consignment::strict_decode(&data[..]).is_err());
+    }
+
+    #[test]
+    fn test_prune_to_validates() {
+        let original = consignment();
+        let endpoint = original
+            .endpoints
+            .first()
+            .expect("test fixture has at least one endpoint")
+            .0;
+
+        let pruned = original.prune_to(&endpoint);
+
+        assert_eq!(pruned.endpoints, vec![original
+            .endpoints
+            .iter()
+            .find(|(node_id, _)| *node_id == endpoint)
+            .unwrap()
+            .clone()]);
+        assert!(pruned.node_ids().contains(&endpoint));
+        assert!(pruned.state_transitions.len() <= original.state_transitions.len());
+        assert!(pruned.state_extensions.len() <= original.state_extensions.len());
+
+        let schema = schema();
+        let status = pruned.validate(&schema, TestResolver);
+        println!("{}", status);
+    }
+
+    #[test]
+    fn test_required_indices_tracks_index_not_whole_type() {
+        let parent = consignment().genesis.node_id();
+
+        // Two descendants share assignment type `7` under `parent`, but
+        // at different indices: index 0 is the one that stays on the
+        // kept path, index 1 belongs to a sibling that got pruned and
+        // must not be conflated with index 0 just because it's the same
+        // type.
+        let mut kept_ancestors = BTreeMap::new();
+        kept_ancestors.insert(parent, {
+            let mut by_type = BTreeMap::new();
+            by_type.insert(7u16, vec![0u16]);
+            by_type
+        });
+
+        let required =
+            Consignment::required_indices(std::iter::once(&kept_ancestors));
+        let kept_indices = required
+            .get(&(parent, 7))
+            .expect("parent/type pair referenced by the kept ancestors map");
+
+        assert!(
+            kept_indices.contains(&0),
+            "index 0 is consumed by the kept descendant and must stay \
+             revealed"
+        );
+        assert!(
+            !kept_indices.contains(&1),
+            "index 1 belongs to a pruned sibling under the same type and \
+             must not be treated as required just because the type \
+             matches"
+        );
+    }
 }
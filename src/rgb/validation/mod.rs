@@ -0,0 +1,20 @@
+// LNP/BP Rust Library
+// Written in 2020 by
+//     Dr. Maxim Orlovsky <orlovsky@pandoracore.com>
+//
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the MIT License
+// along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+mod resolver;
+mod status;
+mod validator;
+
+pub use resolver::{ResolvedTx, SpvProof, TxResolver, TxResolverError};
+pub use status::{Failure, Status, Warning};
+pub use validator::Validator;
@@ -0,0 +1,104 @@
+// LNP/BP Rust Library
+// Written in 2020 by
+//     Dr. Maxim Orlovsky <orlovsky@pandoracore.com>
+//
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the MIT License
+// along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use std::collections::BTreeMap;
+
+use bitcoin::hashes::{sha256d, Hash};
+use bitcoin::{BlockHeader, Transaction, Txid};
+
+/// Failure resolving a transaction id into its on-chain transaction.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Display, Error)]
+#[display(Debug)]
+pub struct TxResolverError;
+
+/// A Bitcoin SPV proof that a transaction is included in a given block,
+/// allowing [`Anchor`](crate::rgb::Anchor) verification against a block
+/// header obtained from a light client instead of a trusted full node's
+/// RPC.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct SpvProof {
+    /// Header of the block the transaction is claimed to belong to.
+    pub header: BlockHeader,
+    /// Merkle branch from the transaction's txid up to the header's
+    /// `merkle_root`, one sibling hash per tree level.
+    pub merkle_branch: Vec<sha256d::Hash>,
+    /// Zero-based position of the transaction within the block, used to
+    /// determine whether each branch hash is a left or right sibling.
+    pub pos: u32,
+}
+
+impl SpvProof {
+    /// Recomputes the Merkle root implied by `txid` and this proof's
+    /// branch, and checks it against [`SpvProof::header`]'s
+    /// `merkle_root`.
+    pub fn verify(&self, txid: Txid) -> bool {
+        let mut pos = self.pos;
+        let mut hash = txid.as_hash();
+        for sibling in &self.merkle_branch {
+            hash = if pos & 1 == 0 {
+                merkle_parent(hash, *sibling)
+            } else {
+                merkle_parent(*sibling, hash)
+            };
+            pos >>= 1;
+        }
+        hash == self.header.merkle_root.as_hash()
+    }
+}
+
+fn merkle_parent(left: sha256d::Hash, right: sha256d::Hash) -> sha256d::Hash {
+    let mut engine = sha256d::Hash::engine();
+    engine.input(&left.into_inner());
+    engine.input(&right.into_inner());
+    sha256d::Hash::from_engine(engine)
+}
+
+/// A transaction resolved by a [`TxResolver`], together with the data a
+/// [`Validator`](crate::rgb::validation::Validator) needs to check it
+/// against the chain: its confirmation height and, when available, an
+/// SPV proof of inclusion that lets validation proceed without a
+/// trusted full node.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ResolvedTx {
+    pub tx: Transaction,
+    pub height: u64,
+    pub spv_proof: Option<SpvProof>,
+}
+
+/// Resolves transaction ids referenced by a consignment (through its
+/// [`Anchor`](crate::rgb::Anchor)s) into the actual on-chain
+/// transactions, so [`Validator`](crate::rgb::validation::Validator) can
+/// check that the anchors it is given commit to real, confirmed
+/// transactions.
+pub trait TxResolver {
+    fn resolve(
+        &self,
+        txid: &Txid,
+    ) -> Result<Option<ResolvedTx>, TxResolverError>;
+
+    /// Resolves many transaction ids in one round trip. Backends that can
+    /// batch lookups (an indexer, a light client's compact filter query)
+    /// should override this; the default falls back to one [`Self::resolve`]
+    /// call per id, so [`Consignment::validate`](crate::rgb::Consignment::validate)
+    /// can always request all of [`Consignment::txids`](crate::rgb::Consignment::txids)
+    /// together regardless of the resolver's capabilities.
+    fn resolve_batch(
+        &self,
+        txids: &[Txid],
+    ) -> Result<BTreeMap<Txid, Option<ResolvedTx>>, TxResolverError> {
+        txids
+            .iter()
+            .map(|txid| Ok((*txid, self.resolve(txid)?)))
+            .collect()
+    }
+}
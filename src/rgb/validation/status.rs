@@ -0,0 +1,73 @@
+// LNP/BP Rust Library
+// Written in 2020 by
+//     Dr. Maxim Orlovsky <orlovsky@pandoracore.com>
+//
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the MIT License
+// along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use bitcoin::Txid;
+
+/// Result of running [`Validator::validate`](super::Validator::validate)
+/// against a consignment: zero or more [`Failure`]s (the consignment is
+/// invalid) and [`Warning`]s (the consignment may still be accepted, but
+/// something about it could not be fully checked).
+#[derive(Clone, PartialEq, Eq, Debug, Display, Default)]
+#[display(Debug)]
+pub struct Status {
+    pub failures: Vec<Failure>,
+    pub warnings: Vec<Warning>,
+}
+
+impl Status {
+    pub fn new() -> Status { Status::default() }
+
+    #[inline]
+    pub fn is_valid(&self) -> bool { self.failures.is_empty() }
+
+    pub(crate) fn add_failure(&mut self, failure: Failure) -> &mut Self {
+        self.failures.push(failure);
+        self
+    }
+
+    pub(crate) fn add_warning(&mut self, warning: Warning) -> &mut Self {
+        self.warnings.push(warning);
+        self
+    }
+}
+
+/// A problem that makes a consignment invalid.
+#[derive(Clone, PartialEq, Eq, Debug, Display)]
+#[display(Debug)]
+pub enum Failure {
+    /// None of the configured [`TxResolver`](super::TxResolver)s were able
+    /// to resolve an anchor's txid to an on-chain transaction.
+    TxidNotFound(Txid),
+
+    /// The transaction a resolver returned for an anchor's txid does not
+    /// actually commit to the node that anchor is supposed to prove. A
+    /// resolver only confirming that *some* transaction exists at that
+    /// txid, without this check, would let a malicious one substitute an
+    /// unrelated (but genuinely confirmed) transaction.
+    AnchorCommitmentMismatch(Txid),
+
+    /// A resolver returned an [`SpvProof`](super::SpvProof) for an
+    /// anchor's txid, but recomputing the Merkle root from that proof did
+    /// not match the claimed block header — the resolver (e.g. an
+    /// untrusted light client) is lying about chain inclusion, so the
+    /// anchor cannot be trusted regardless of the commitment check above.
+    SpvProofInvalid(Txid),
+}
+
+/// A problem that does not by itself invalidate a consignment, but that a
+/// caller relying on the result should be aware of. Currently unused —
+/// reserved for findings that shouldn't fail validation outright, such
+/// as a resolver declining to provide confirmation height.
+#[derive(Clone, PartialEq, Eq, Debug, Display)]
+#[display(Debug)]
+pub enum Warning {}
@@ -0,0 +1,77 @@
+// LNP/BP Rust Library
+// Written in 2020 by
+//     Dr. Maxim Orlovsky <orlovsky@pandoracore.com>
+//
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the MIT License
+// along with this software.
+// If not, see <https://opensource.org/licenses/MIT>.
+
+use crate::rgb::{Consignment, Node, Schema};
+
+use super::{Failure, Status, TxResolver};
+
+/// Validates a [`Consignment`] against a [`Schema`].
+///
+/// Transaction data needed for validation is fetched from a
+/// [`TxResolver`] in a single batched round trip via
+/// [`TxResolver::resolve_batch`] rather than one call per txid. For each
+/// anchor this checks, in order: that its txid resolved to a
+/// transaction at all; that the transaction actually commits to the
+/// anchored node (not merely that *some* transaction exists at that
+/// txid — a resolver could otherwise substitute an unrelated but
+/// genuinely confirmed transaction); and, when the resolver supplied an
+/// [`SpvProof`](super::SpvProof) (a light client rather than a trusted
+/// full node), that the proof's Merkle root matches its header.
+pub struct Validator;
+
+impl Validator {
+    pub fn validate<R: TxResolver>(
+        _schema: &Schema,
+        consignment: &Consignment,
+        resolver: R,
+    ) -> Status {
+        let mut status = Status::new();
+
+        let txids: Vec<_> = consignment.txids().into_iter().collect();
+        let resolved = match resolver.resolve_batch(&txids) {
+            Ok(resolved) => resolved,
+            Err(_) => {
+                for txid in txids {
+                    status.add_failure(Failure::TxidNotFound(txid));
+                }
+                return status;
+            }
+        };
+
+        for (anchor, transition) in &consignment.state_transitions {
+            let resolved_tx = match resolved.get(&anchor.txid) {
+                Some(Some(resolved_tx)) => resolved_tx,
+                _ => {
+                    status.add_failure(Failure::TxidNotFound(anchor.txid));
+                    continue;
+                }
+            };
+
+            if !anchor.verify(transition.node_id(), &resolved_tx.tx) {
+                status.add_failure(Failure::AnchorCommitmentMismatch(
+                    anchor.txid,
+                ));
+                continue;
+            }
+
+            if let Some(proof) = &resolved_tx.spv_proof {
+                if !proof.verify(anchor.txid) {
+                    status
+                        .add_failure(Failure::SpvProofInvalid(anchor.txid));
+                }
+            }
+        }
+
+        status
+    }
+}